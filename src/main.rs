@@ -1,62 +1,276 @@
-use h3_world_geometry_generator::gen_world_chunks;
-use std::env;
+use clap::{Args, Parser, Subcommand};
+use h3_world_geometry_generator::{
+    collect_cells_at_resolution, export_geojson, export_gltf, gen_world_chunks,
+    gen_world_geometry, repair, validate, ColorMode, ExportFormat,
+};
+
+/// Vertices within this distance are considered coincident when reporting duplicates
+/// via `--validate`. Matches the weld epsilon `--repair` uses internally for chunks.
+const VALIDATE_WELD_EPSILON: f32 = 1e-4;
 
 fn main() {
-    match run() {
-        Ok(_) => println!("Successfully created H3 chunks!"),
-        Err(e) => eprintln!("Error creating mesh: {}", e),
+    let cli = Cli::parse();
+
+    let result = match cli.command {
+        Commands::Gltf(args) => run_gltf(args),
+        Commands::Geojson(args) => run_geojson(args),
+        Commands::Chunks(args) => run_chunks(args),
+    };
+
+    if let Err(e) = result {
+        eprintln!("Error creating mesh: {}", e);
+        std::process::exit(1);
+    }
+}
+
+/// Generate H3 world geometry and export it to glTF, GeoJSON, or chunked files.
+#[derive(Parser)]
+#[command(name = "h3-world-geometry-generator", version, about)]
+struct Cli {
+    #[command(subcommand)]
+    command: Commands,
+}
+
+#[derive(Subcommand)]
+enum Commands {
+    /// Generate a single triangulated glTF mesh at one resolution.
+    Gltf(GltfArgs),
+    /// Generate a single GeoJSON FeatureCollection at one resolution.
+    Geojson(GeojsonArgs),
+    /// Generate geometry split into chunks, one output file set per chunk.
+    Chunks(ChunksArgs),
+}
+
+#[derive(Args)]
+struct GltfArgs {
+    #[command(flatten)]
+    common: CommonArgs,
+
+    /// Per-vertex coloring scheme written into the glTF `COLOR_0` accessor.
+    #[arg(long, value_enum, default_value_t = ColorMode::Random)]
+    color_mode: ColorMode,
+
+    /// Run the validate-and-repair pass on the mesh before export.
+    #[arg(long)]
+    repair: bool,
+
+    /// Report non-finite vertices, degenerate triangles, orphan vertices, and
+    /// near-duplicate vertices found in the mesh before export, without fixing them.
+    #[arg(long)]
+    validate: bool,
+}
+
+#[derive(Args)]
+struct GeojsonArgs {
+    #[command(flatten)]
+    common: CommonArgs,
+}
+
+#[derive(Args)]
+struct ChunksArgs {
+    #[command(flatten)]
+    common: CommonArgs,
+
+    /// Coarser H3 resolution used to split work into chunks (must be lower than --world-res).
+    #[arg(long, value_parser = parse_resolution, default_value_t = 0)]
+    chunk_res: u8,
+
+    /// Which file(s) to write per chunk.
+    #[arg(long, value_enum, default_value_t = ExportFormat::Gltf)]
+    format: ExportFormat,
+
+    /// Per-vertex coloring scheme written into each chunk's glTF `COLOR_0` accessor.
+    #[arg(long, value_enum, default_value_t = ColorMode::Random)]
+    color_mode: ColorMode,
+
+    /// Run the validate-and-repair pass on each chunk mesh before export.
+    #[arg(long)]
+    repair: bool,
+
+    /// Report non-finite vertices, degenerate triangles, orphan vertices, and
+    /// near-duplicate vertices found in each chunk mesh before export, without fixing them.
+    #[arg(long)]
+    validate: bool,
+
+    /// Also write a LOD chain glTF (`-lods.gltf`) per chunk, one primitive per ratio
+    /// (e.g. `--lods 1.0,0.5,0.25`) via quadric edge-collapse simplification. Only
+    /// written when `--format` includes glTF.
+    #[arg(long, value_delimiter = ',')]
+    lods: Option<Vec<f32>>,
+
+    /// Also write a meshlet-partitioned glTF (`-meshlets.gltf`) per chunk, one
+    /// primitive per cluster, using the default vertex/triangle caps. Only written
+    /// when `--format` includes glTF.
+    #[arg(long)]
+    meshlets: bool,
+}
+
+/// Flags shared by every subcommand.
+#[derive(Args)]
+struct CommonArgs {
+    /// Radius of the sphere used when projecting vertices.
+    #[arg(long, value_parser = parse_positive_radius, default_value_t = 10.0)]
+    sphere_radius: f64,
+
+    /// H3 grid resolution at which geometry is generated (0..=15).
+    #[arg(long, value_parser = parse_resolution, default_value_t = 0)]
+    world_res: u8,
+
+    /// Filename prefix for exported files. `chunks` writes under `output/<output>/`;
+    /// `gltf` and `geojson` write `<output>.gltf`/`<output>.geojson` directly.
+    #[arg(long, default_value = "output")]
+    output: String,
+}
+
+/// Validate an H3 resolution string is an integer in `0..=15`.
+fn parse_resolution(s: &str) -> Result<u8, String> {
+    let value: u8 = s.parse().map_err(|_| format!("`{}` is not a valid integer", s))?;
+    if value > 15 {
+        return Err(format!("resolution must be in 0..=15, got {}", value));
+    }
+    Ok(value)
+}
+
+/// Validate a sphere radius string is a finite, positive number.
+fn parse_positive_radius(s: &str) -> Result<f64, String> {
+    let value: f64 = s.parse().map_err(|_| format!("`{}` is not a valid number", s))?;
+    if !value.is_finite() || value <= 0.0 {
+        return Err(format!("sphere radius must be positive, got {}", value));
+    }
+    Ok(value)
+}
+
+fn run_gltf(args: GltfArgs) -> Result<(), Box<dyn std::error::Error>> {
+    let (mut mesh, stats) = gen_world_geometry(args.common.sphere_radius, args.common.world_res)?;
+
+    h3_world_geometry_generator::apply_color_mode(&mut mesh, args.color_mode, args.common.sphere_radius);
+
+    if args.validate {
+        print_validation_report(&validate(&mesh, VALIDATE_WELD_EPSILON));
     }
+
+    if args.repair {
+        let repair_counts = repair(&mut mesh, None);
+        println!(
+            "Repaired mesh: {} degenerate triangles removed, {} orphan vertices dropped, {} vertices welded",
+            repair_counts.degenerate_triangles_removed,
+            repair_counts.orphan_vertices_dropped,
+            repair_counts.vertices_welded
+        );
+    }
+
+    mesh.compute_normals();
+
+    let gltf_path = format!("{}.gltf", args.common.output);
+    let bin_path = format!("{}.bin", args.common.output);
+    export_gltf(&mesh, &gltf_path, &bin_path)?;
+    println!("Exported {} & {}", gltf_path, bin_path);
+
+    print_stats(&stats);
+    Ok(())
 }
 
-fn run() -> Result<(), Box<dyn std::error::Error>> {
-    const SPHERE_RADIUS: f64 = 10.0;
+fn run_geojson(args: GeojsonArgs) -> Result<(), Box<dyn std::error::Error>> {
+    let cells = collect_cells_at_resolution(args.common.world_res)?;
 
-    let (world_res, chunk_res, output_prefix) = parse_cli_args();
+    let geojson_path = format!("{}.geojson", args.common.output);
+    export_geojson(&cells, 0, &geojson_path)?;
+    println!("Exported {}", geojson_path);
 
-    println!("World resolution: {}", world_res);
-    println!("Chunk resolution: {}", chunk_res);
-    println!("Output prefix: {}", output_prefix);
+    Ok(())
+}
 
-    let stats = gen_world_chunks(SPHERE_RADIUS, world_res, chunk_res, &output_prefix)?;
+fn run_chunks(args: ChunksArgs) -> Result<(), Box<dyn std::error::Error>> {
+    if let Some(ratios) = &args.lods {
+        if ratios.is_empty() {
+            return Err("--lods requires at least one ratio".into());
+        }
+    }
+
+    let stats = gen_world_chunks(
+        args.common.sphere_radius,
+        args.common.world_res,
+        args.chunk_res,
+        &args.common.output,
+        args.format,
+        args.color_mode,
+        args.repair,
+        args.validate,
+        args.lods.as_deref(),
+        args.meshlets,
+    )?;
+
+    print_stats(&stats);
+    if args.repair {
+        println!("  - Degenerate triangles removed: {}", stats.degenerate_triangles_removed);
+        println!("  - Orphan vertices dropped: {}", stats.orphan_vertices_dropped);
+        println!("  - Vertices welded: {}", stats.vertices_welded);
+    }
+
+    Ok(())
+}
+
+/// Print a `ValidationReport`'s finding counts, one line per non-empty category.
+fn print_validation_report(report: &h3_world_geometry_generator::ValidationReport) {
+    if report.is_clean() {
+        println!("Validation: no issues found");
+        return;
+    }
 
+    println!("Validation found issues:");
+    if !report.non_finite_vertices.is_empty() {
+        println!("  - Non-finite vertices: {}", report.non_finite_vertices.len());
+    }
+    if !report.degenerate_triangles.is_empty() {
+        println!("  - Degenerate triangles: {}", report.degenerate_triangles.len());
+    }
+    if !report.out_of_range_indices.is_empty() {
+        println!("  - Out-of-range triangle indices: {}", report.out_of_range_indices.len());
+    }
+    if !report.orphan_vertices.is_empty() {
+        println!("  - Orphan vertices: {}", report.orphan_vertices.len());
+    }
+    if !report.duplicate_vertices.is_empty() {
+        println!("  - Near-duplicate vertices: {}", report.duplicate_vertices.len());
+    }
+}
+
+fn print_stats(stats: &h3_world_geometry_generator::ProcessingStats) {
     println!("\nProcessing completed:");
     println!("  - Cells processed: {}", stats.cells_processed);
     println!("  - Pentagons: {}", stats.pentagon_count);
     println!("  - Hexagons: {}", stats.hexagon_count);
     println!("  - Invalid coordinates: {}", stats.invalid_coords);
-
-    Ok(())
 }
 
-/// Parse CLI arguments.
-/// 
-/// Returns `(world_resolution, chunk_resolution, output_prefix)`
-/// * `world_resolution` – H3 grid resolution for geometry (defaults to 0)
-/// * `chunk_resolution` – resolution used to split geometry into chunks (defaults to 0)
-/// * `output_prefix` – filename prefix for exported files (defaults to "output")
-fn parse_cli_args() -> (u8, u8, String) {
-    let args: Vec<String> = env::args().collect();
-
-    // First optional arg: world resolution
-    let world_resolution = if args.len() > 1 {
-        args[1].parse::<u8>().unwrap_or(0)
-    } else {
-        0
-    };
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    // Second optional arg: chunk resolution
-    let chunk_resolution = if args.len() > 2 {
-        args[2].parse::<u8>().unwrap_or(0)
-    } else {
-        0
-    };
+    #[test]
+    fn parse_resolution_accepts_the_full_h3_range() {
+        assert_eq!(parse_resolution("0"), Ok(0));
+        assert_eq!(parse_resolution("15"), Ok(15));
+    }
 
-    // Third optional arg: output filename prefix
-    let output_prefix = if args.len() > 3 {
-        args[3].clone()
-    } else {
-        String::from("output")
-    };
+    #[test]
+    fn parse_resolution_rejects_out_of_range_and_non_integer() {
+        assert!(parse_resolution("16").is_err());
+        assert!(parse_resolution("-1").is_err());
+        assert!(parse_resolution("abc").is_err());
+    }
 
-    (world_resolution, chunk_resolution, output_prefix)
-}
\ No newline at end of file
+    #[test]
+    fn parse_positive_radius_accepts_finite_positive_values() {
+        assert_eq!(parse_positive_radius("10.5"), Ok(10.5));
+    }
+
+    #[test]
+    fn parse_positive_radius_rejects_non_positive_and_non_finite() {
+        assert!(parse_positive_radius("0").is_err());
+        assert!(parse_positive_radius("-1.0").is_err());
+        assert!(parse_positive_radius("NaN").is_err());
+        assert!(parse_positive_radius("inf").is_err());
+        assert!(parse_positive_radius("not-a-number").is_err());
+    }
+}
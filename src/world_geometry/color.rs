@@ -1,11 +1,58 @@
 use rand::Rng;
 
+use crate::mesh::Mesh;
+
+/// Which per-vertex coloring scheme `apply_color_mode` writes into a mesh's `colors`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+#[value(rename_all = "kebab-case")]
+pub enum ColorMode {
+    /// A random bright color per vertex.
+    Random,
+    /// A banded color keyed to latitude (poles / temperate / equatorial).
+    LatitudeBanded,
+    /// A single flat color for the whole mesh.
+    Solid,
+}
+
+/// Flat gray used for `ColorMode::Solid`.
+const SOLID_COLOR: [f32; 3] = [0.8, 0.8, 0.8];
+
 /// Generate a random bright color
 pub fn generate_random_color() -> [f32; 3] {
     let mut rng = rand::rng();
     [
         rng.random_range(0.3..1.0), // Red
-        rng.random_range(0.3..1.0), // Green  
+        rng.random_range(0.3..1.0), // Green
         rng.random_range(0.3..1.0), // Blue
     ]
-} 
\ No newline at end of file
+}
+
+/// Pick a banded color from a vertex's normalized height on the sphere
+/// (`y / sphere_radius`, i.e. `sin(latitude)`).
+fn latitude_band_color(normalized_y: f32) -> [f32; 3] {
+    const POLAR: [f32; 3] = [0.9, 0.9, 0.95];
+    const TEMPERATE: [f32; 3] = [0.5, 0.7, 0.4];
+    const EQUATORIAL: [f32; 3] = [0.8, 0.75, 0.4];
+
+    if normalized_y > 0.66 || normalized_y < -0.66 {
+        POLAR
+    } else if normalized_y > 0.33 || normalized_y < -0.33 {
+        TEMPERATE
+    } else {
+        EQUATORIAL
+    }
+}
+
+/// Populate `mesh.colors` with one entry per vertex according to `mode`.
+/// `sphere_radius` is used to normalize vertex height for `ColorMode::LatitudeBanded`.
+pub fn apply_color_mode(mesh: &mut Mesh, mode: ColorMode, sphere_radius: f64) {
+    mesh.colors = mesh
+        .vertices
+        .iter()
+        .map(|vertex| match mode {
+            ColorMode::Random => generate_random_color(),
+            ColorMode::LatitudeBanded => latitude_band_color((vertex[1] as f64 / sphere_radius) as f32),
+            ColorMode::Solid => SOLID_COLOR,
+        })
+        .collect();
+}
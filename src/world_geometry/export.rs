@@ -1,51 +1,185 @@
-use serde_json::json;
+use h3o::CellIndex;
+use serde_json::{json, Value};
 use std::{fs::File, io::Write, path::Path};
 use crate::mesh::Mesh;
+use crate::meshlet::Meshlet;
 
-/// Write mesh data to binary format
-pub fn write_binary_data(mesh: &Mesh, filename: &str) -> Result<(), Box<dyn std::error::Error>> {
-    let mut binary_data = Vec::new();
-    
-    // Write vertices (3 floats per vertex, 4 bytes per float)
-    for vertex in &mesh.vertices {
+/// Append a mesh's position/UV/(optional color)/(optional normal)/index data to a
+/// shared binary buffer and record the matching `bufferViews`/`accessors` entries,
+/// returning the (position, uv, color, normal, index) accessor indices for use in a
+/// primitive. Shared by every glTF exporter (single mesh, LOD chain, meshlets) so
+/// each primitive gets its own slice of one `.bin` file.
+fn append_mesh_buffers(
+    vertices: &[[f32; 3]],
+    uvs: &[[f32; 2]],
+    colors: Option<&[[f32; 3]]>,
+    normals: Option<&[[f32; 3]]>,
+    indices: &[usize],
+    binary_data: &mut Vec<u8>,
+    buffer_views: &mut Vec<Value>,
+    accessors: &mut Vec<Value>,
+) -> (usize, usize, Option<usize>, Option<usize>, usize) {
+    let vertex_offset = binary_data.len();
+    for vertex in vertices {
         binary_data.extend_from_slice(&vertex[0].to_le_bytes());
         binary_data.extend_from_slice(&vertex[1].to_le_bytes());
         binary_data.extend_from_slice(&vertex[2].to_le_bytes());
     }
-    
-    // Write UV0 (2 floats per vertex)
-    for uv in &mesh.uvs0 {
+    let vertex_buffer_size = vertices.len() * 12;
+    buffer_views.push(json!({
+        "buffer": 0,
+        "byteOffset": vertex_offset,
+        "byteLength": vertex_buffer_size,
+        "byteStride": 12,
+        "target": 34962
+    }));
+    let position_accessor = accessors.len();
+    accessors.push(json!({
+        "bufferView": buffer_views.len() - 1,
+        "componentType": 5126,
+        "count": vertices.len(),
+        "type": "VEC3",
+        "byteOffset": 0
+    }));
+
+    let uv_offset = binary_data.len();
+    for uv in uvs {
         binary_data.extend_from_slice(&uv[0].to_le_bytes());
         binary_data.extend_from_slice(&uv[1].to_le_bytes());
     }
-    
-    // Write indices (4 bytes per index)
-    for &index in &mesh.indices {
+    let uv_buffer_size = uvs.len() * 8;
+    buffer_views.push(json!({
+        "buffer": 0,
+        "byteOffset": uv_offset,
+        "byteLength": uv_buffer_size,
+        "byteStride": 8,
+        "target": 34962
+    }));
+    let uv_accessor = accessors.len();
+    accessors.push(json!({
+        "bufferView": buffer_views.len() - 1,
+        "componentType": 5126,
+        "count": uvs.len(),
+        "type": "VEC2",
+        "byteOffset": 0
+    }));
+
+    let color_accessor = colors.map(|colors| {
+        let color_offset = binary_data.len();
+        for color in colors {
+            binary_data.extend_from_slice(&color[0].to_le_bytes());
+            binary_data.extend_from_slice(&color[1].to_le_bytes());
+            binary_data.extend_from_slice(&color[2].to_le_bytes());
+        }
+        let color_buffer_size = colors.len() * 12;
+        buffer_views.push(json!({
+            "buffer": 0,
+            "byteOffset": color_offset,
+            "byteLength": color_buffer_size,
+            "byteStride": 12,
+            "target": 34962
+        }));
+        let accessor = accessors.len();
+        accessors.push(json!({
+            "bufferView": buffer_views.len() - 1,
+            "componentType": 5126,
+            "count": colors.len(),
+            "type": "VEC3",
+            "byteOffset": 0
+        }));
+        accessor
+    });
+
+    let normal_accessor = normals.map(|normals| {
+        let normal_offset = binary_data.len();
+        for normal in normals {
+            binary_data.extend_from_slice(&normal[0].to_le_bytes());
+            binary_data.extend_from_slice(&normal[1].to_le_bytes());
+            binary_data.extend_from_slice(&normal[2].to_le_bytes());
+        }
+        let normal_buffer_size = normals.len() * 12;
+        buffer_views.push(json!({
+            "buffer": 0,
+            "byteOffset": normal_offset,
+            "byteLength": normal_buffer_size,
+            "byteStride": 12,
+            "target": 34962
+        }));
+        let accessor = accessors.len();
+        accessors.push(json!({
+            "bufferView": buffer_views.len() - 1,
+            "componentType": 5126,
+            "count": normals.len(),
+            "type": "VEC3",
+            "byteOffset": 0
+        }));
+        accessor
+    });
+
+    let index_offset = binary_data.len();
+    for &index in indices {
         binary_data.extend_from_slice(&(index as u32).to_le_bytes());
     }
-    
-    let mut file = File::create(filename)?;
-    file.write_all(&binary_data)?;
-    Ok(())
+    let index_buffer_size = indices.len() * 4;
+    buffer_views.push(json!({
+        "buffer": 0,
+        "byteOffset": index_offset,
+        "byteLength": index_buffer_size,
+        "target": 34963
+    }));
+    let index_accessor = accessors.len();
+    accessors.push(json!({
+        "bufferView": buffer_views.len() - 1,
+        "componentType": 5125,
+        "count": indices.len(),
+        "type": "SCALAR",
+        "byteOffset": 0
+    }));
+
+    (position_accessor, uv_accessor, color_accessor, normal_accessor, index_accessor)
 }
 
-/// Export mesh as GLTF format
+/// Export mesh as GLTF format. Writes a `COLOR_0` accessor when `mesh.colors` has been
+/// populated (see `color::apply_color_mode`) and a `NORMAL` accessor when
+/// `mesh.normals` has been populated (see `Mesh::compute_normals`).
 pub fn export_gltf(mesh: &Mesh, gltf_filename: &str, binary_filename: &str) -> Result<(), Box<dyn std::error::Error>> {
-    // Write binary data first
-    write_binary_data(mesh, binary_filename)?;
-    
-    // Calculate buffer sizes
-    let vertex_buffer_size = mesh.vertices.len() * 12; // 3 floats * 4 bytes
-    let uv_buffer_size = mesh.uvs0.len() * 8; // 2 floats * 4 bytes
-    let index_buffer_size = mesh.indices.len() * 4; // 1 u32 * 4 bytes
-    let total_buffer_size = vertex_buffer_size + uv_buffer_size + index_buffer_size;
-    
+    let mut binary_data = Vec::new();
+    let mut buffer_views = Vec::new();
+    let mut accessors = Vec::new();
+
+    let colors = (!mesh.colors.is_empty()).then_some(mesh.colors.as_slice());
+    let normals = (!mesh.normals.is_empty()).then_some(mesh.normals.as_slice());
+    let (position_accessor, uv_accessor, color_accessor, normal_accessor, index_accessor) = append_mesh_buffers(
+        &mesh.vertices,
+        &mesh.uvs0,
+        colors,
+        normals,
+        &mesh.indices,
+        &mut binary_data,
+        &mut buffer_views,
+        &mut accessors,
+    );
+
+    let mut file = File::create(binary_filename)?;
+    file.write_all(&binary_data)?;
+
     // Only keep the file name portion for the URI stored in GLTF
     let binary_uri = Path::new(binary_filename)
         .file_name()
         .and_then(|os_str| os_str.to_str())
         .ok_or("Invalid binary filename")?;
-    
+
+    let mut attributes = json!({
+        "POSITION": position_accessor,
+        "TEXCOORD_0": uv_accessor
+    });
+    if let Some(color_accessor) = color_accessor {
+        attributes["COLOR_0"] = json!(color_accessor);
+    }
+    if let Some(normal_accessor) = normal_accessor {
+        attributes["NORMAL"] = json!(normal_accessor);
+    }
+
     // Create GLTF JSON
     let gltf = json!({
         "asset": {
@@ -70,72 +204,286 @@ pub fn export_gltf(mesh: &Mesh, gltf_filename: &str, binary_filename: &str) -> R
                 "name": "H3_Polyhedron",
                 "primitives": [
                     {
-                        "attributes": {
-                            "POSITION": 0,
-                            "TEXCOORD_0": 1
-                        },
-                        "indices": 2,
+                        "attributes": attributes,
+                        "indices": index_accessor,
                         "mode": 4
                     }
                 ]
             }
         ],
-        "accessors": [
+        "accessors": accessors,
+        "bufferViews": buffer_views,
+        "buffers": [
             {
-                "bufferView": 0,
-                "componentType": 5126,
-                "count": mesh.vertices.len(),
-                "type": "VEC3",
-                "byteOffset": 0
+                "byteLength": binary_data.len(),
+                "uri": binary_uri
+            }
+        ]
+    });
+
+    // Write GLTF file
+    let gltf_string = serde_json::to_string_pretty(&gltf)?;
+    std::fs::write(gltf_filename, gltf_string)?;
+
+    Ok(())
+}
+
+/// Export a LOD chain (as produced by `simplify::generate_lod_chain`) as a single glTF
+/// mesh whose primitives are tagged LOD0..LODn via `extras`. All LOD buffers are packed
+/// back-to-back into one `.bin` file.
+pub fn export_gltf_lods(
+    lods: &[Mesh],
+    gltf_filename: &str,
+    binary_filename: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    if lods.is_empty() {
+        return Err("export_gltf_lods requires at least one LOD mesh".into());
+    }
+
+    let mut binary_data = Vec::new();
+    let mut buffer_views = Vec::new();
+    let mut accessors = Vec::new();
+    let mut primitives = Vec::new();
+
+    for (lod_index, mesh) in lods.iter().enumerate() {
+        let (position_accessor, uv_accessor, _color_accessor, _normal_accessor, index_accessor) = append_mesh_buffers(
+            &mesh.vertices,
+            &mesh.uvs0,
+            None,
+            None,
+            &mesh.indices,
+            &mut binary_data,
+            &mut buffer_views,
+            &mut accessors,
+        );
+
+        primitives.push(json!({
+            "attributes": {
+                "POSITION": position_accessor,
+                "TEXCOORD_0": uv_accessor
             },
+            "indices": index_accessor,
+            "mode": 4,
+            "extras": {
+                "lod": lod_index,
+                "name": format!("LOD{}", lod_index)
+            }
+        }));
+    }
+
+    let binary_uri = Path::new(binary_filename)
+        .file_name()
+        .and_then(|os_str| os_str.to_str())
+        .ok_or("Invalid binary filename")?;
+
+    let gltf = json!({
+        "asset": {
+            "version": "2.0",
+            "generator": "DTD_WorldGenerator"
+        },
+        "scene": 0,
+        "scenes": [
             {
-                "bufferView": 1,
-                "componentType": 5126,
-                "count": mesh.uvs0.len(),
-                "type": "VEC2",
-                "byteOffset": 0
-            },
+                "name": "H3_Scene",
+                "nodes": [0]
+            }
+        ],
+        "nodes": [
             {
-                "bufferView": 2,
-                "componentType": 5125,
-                "count": mesh.indices.len(),
-                "type": "SCALAR",
-                "byteOffset": 0
+                "name": "H3_Polyhedron_Node",
+                "mesh": 0
             }
         ],
-        "bufferViews": [
+        "meshes": [
             {
-                "buffer": 0,
-                "byteOffset": 0,
-                "byteLength": vertex_buffer_size,
-                "byteStride": 12,
-                "target": 34962
-            },
+                "name": "H3_Polyhedron_LODs",
+                "primitives": primitives
+            }
+        ],
+        "accessors": accessors,
+        "bufferViews": buffer_views,
+        "buffers": [
             {
-                "buffer": 0,
-                "byteOffset": vertex_buffer_size,
-                "byteLength": uv_buffer_size,
-                "byteStride": 8,
-                "target": 34962
+                "byteLength": binary_data.len(),
+                "uri": binary_uri
+            }
+        ]
+    });
+
+    let mut file = File::create(binary_filename)?;
+    file.write_all(&binary_data)?;
+
+    let gltf_string = serde_json::to_string_pretty(&gltf)?;
+    std::fs::write(gltf_filename, gltf_string)?;
+
+    Ok(())
+}
+
+/// Build a single GeoJSON `Feature` for an H3 cell, using its true boundary
+/// (`cell.boundary()`, requires h3o's `geo` feature) rather than the triangulated fan
+/// approximation used for the glTF mesh.
+pub fn geojson_feature(cell: CellIndex, chunk_id: usize) -> Value {
+    let mut ring: Vec<[f64; 2]> = cell
+        .boundary()
+        .iter()
+        .map(|vertex| [vertex.lng(), vertex.lat()])
+        .collect();
+    if let Some(&first) = ring.first() {
+        ring.push(first); // GeoJSON polygon rings must be closed
+    }
+
+    json!({
+        "type": "Feature",
+        "geometry": {
+            "type": "Polygon",
+            "coordinates": [ring]
+        },
+        "properties": {
+            "h3_index": cell.to_string(),
+            "is_pentagon": cell.is_pentagon(),
+            "chunk_id": chunk_id
+        }
+    })
+}
+
+/// Export a chunk's cells as a GeoJSON `FeatureCollection` sidecar alongside its
+/// `.gltf`, one `Polygon` feature per cell carrying the H3 index, pentagon flag, and
+/// owning chunk id in `properties`.
+pub fn export_geojson(cells: &[CellIndex], chunk_id: usize, filename: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let features: Vec<Value> = cells.iter().map(|&cell| geojson_feature(cell, chunk_id)).collect();
+
+    let collection = json!({
+        "type": "FeatureCollection",
+        "features": features
+    });
+
+    std::fs::write(filename, serde_json::to_string_pretty(&collection)?)?;
+    Ok(())
+}
+
+/// Export a mesh's meshlet partition (as produced by `meshlet::build_meshlets`) as a
+/// glTF mesh with one primitive per meshlet. Each primitive carries its local vertex
+/// remap size and bounding sphere in `extras` so a meshlet-aware renderer can recover
+/// cluster culling data without re-deriving it.
+pub fn export_gltf_meshlets(
+    mesh: &Mesh,
+    meshlets: &[Meshlet],
+    gltf_filename: &str,
+    binary_filename: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    if meshlets.is_empty() {
+        return Err("export_gltf_meshlets requires at least one meshlet".into());
+    }
+
+    let mut binary_data = Vec::new();
+    let mut buffer_views = Vec::new();
+    let mut accessors = Vec::new();
+    let mut primitives = Vec::new();
+
+    for (meshlet_index, meshlet) in meshlets.iter().enumerate() {
+        let local_vertices: Vec<[f32; 3]> = meshlet.vertex_remap.iter().map(|&v| mesh.vertices[v]).collect();
+        let local_uvs: Vec<[f32; 2]> = meshlet.vertex_remap.iter().map(|&v| mesh.uvs0[v]).collect();
+        let local_indices: Vec<usize> = meshlet
+            .local_indices
+            .iter()
+            .flat_map(|tri| tri.iter().map(|&i| i as usize))
+            .collect();
+
+        let (position_accessor, uv_accessor, _color_accessor, _normal_accessor, index_accessor) = append_mesh_buffers(
+            &local_vertices,
+            &local_uvs,
+            None,
+            None,
+            &local_indices,
+            &mut binary_data,
+            &mut buffer_views,
+            &mut accessors,
+        );
+
+        primitives.push(json!({
+            "attributes": {
+                "POSITION": position_accessor,
+                "TEXCOORD_0": uv_accessor
             },
+            "indices": index_accessor,
+            "mode": 4,
+            "extras": {
+                "meshlet": meshlet_index,
+                "vertexCount": meshlet.vertex_remap.len(),
+                "triangleCount": meshlet.local_indices.len(),
+                "bounds": {
+                    "center": meshlet.bounds.center,
+                    "radius": meshlet.bounds.radius
+                }
+            }
+        }));
+    }
+
+    let binary_uri = Path::new(binary_filename)
+        .file_name()
+        .and_then(|os_str| os_str.to_str())
+        .ok_or("Invalid binary filename")?;
+
+    let gltf = json!({
+        "asset": {
+            "version": "2.0",
+            "generator": "DTD_WorldGenerator"
+        },
+        "scene": 0,
+        "scenes": [
+            {
+                "name": "H3_Scene",
+                "nodes": [0]
+            }
+        ],
+        "nodes": [
+            {
+                "name": "H3_Polyhedron_Node",
+                "mesh": 0
+            }
+        ],
+        "meshes": [
             {
-                "buffer": 0,
-                "byteOffset": vertex_buffer_size + uv_buffer_size,
-                "byteLength": index_buffer_size,
-                "target": 34963
+                "name": "H3_Polyhedron_Meshlets",
+                "primitives": primitives
             }
         ],
+        "accessors": accessors,
+        "bufferViews": buffer_views,
         "buffers": [
             {
-                "byteLength": total_buffer_size,
+                "byteLength": binary_data.len(),
                 "uri": binary_uri
             }
         ]
     });
-    
-    // Write GLTF file
+
+    let mut file = File::create(binary_filename)?;
+    file.write_all(&binary_data)?;
+
     let gltf_string = serde_json::to_string_pretty(&gltf)?;
     std::fs::write(gltf_filename, gltf_string)?;
-    
+
     Ok(())
-} 
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn geojson_feature_closes_the_boundary_ring() {
+        let cell = CellIndex::base_cells().next().expect("h3 has at least one base cell");
+
+        let feature = geojson_feature(cell, 3);
+
+        let ring = feature["geometry"]["coordinates"][0].as_array().expect("coordinates[0] must be an array");
+        let boundary_len = cell.boundary().iter().count();
+
+        assert_eq!(ring.len(), boundary_len + 1, "a closed ring repeats the first vertex once at the end");
+        assert_eq!(ring.first(), ring.last(), "first and last ring points must match exactly");
+        assert_eq!(feature["properties"]["chunk_id"], 3);
+        assert_eq!(feature["properties"]["h3_index"], cell.to_string());
+        assert_eq!(feature["properties"]["is_pentagon"], cell.is_pentagon());
+    }
+}
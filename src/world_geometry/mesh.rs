@@ -1,4 +1,4 @@
-
+use std::collections::HashMap;
 
 /// Mesh data structure containing vertices, colors, and indices
 #[derive(Default)]
@@ -6,6 +6,16 @@ pub struct Mesh {
     pub vertices: Vec<[f32; 3]>,
     pub uvs0: Vec<[f32; 2]>,
     pub indices: Vec<usize>,
+    /// One RGB color per vertex, written as glTF `COLOR_0` when present. Empty unless
+    /// a color mode has been applied via `color::apply_color_mode`.
+    pub colors: Vec<[f32; 3]>,
+    /// One normal per vertex, written as glTF `NORMAL` when present. Empty until
+    /// `compute_normals` has been called.
+    pub normals: Vec<[f32; 3]>,
+    /// Maps a canonical vertex key (e.g. an H3 `VertexIndex`) to the mesh vertex index
+    /// that was already emitted for it, so adjacent cells can share vertices instead
+    /// of duplicating them.
+    vertex_cache: HashMap<u64, usize>,
 }
 
 impl Mesh {
@@ -22,6 +32,22 @@ impl Mesh {
         index
     }
 
+    /// Add a vertex, deduplicating against previously-seen vertices that share `key`.
+    ///
+    /// If `key` was already added, the existing vertex index is returned and no new
+    /// vertex is pushed; otherwise the vertex is added and its index is cached under
+    /// `key` for future lookups. This is what lets adjacent H3 cells weld their shared
+    /// corners into a single mesh vertex instead of each emitting their own copy.
+    pub fn add_vertex_indexed(&mut self, key: u64, vertex: [f32; 3], uv: [f32; 2]) -> usize {
+        if let Some(&index) = self.vertex_cache.get(&key) {
+            return index;
+        }
+
+        let index = self.add_vertex(vertex, uv);
+        self.vertex_cache.insert(key, index);
+        index
+    }
+
 
     /// Add a triangle to the mesh
     pub fn add_triangle(&mut self, triangle: [usize; 3]) {
@@ -35,6 +61,70 @@ impl Mesh {
             triangle_count: self.indices.len() / 3,
         }
     }
+
+    /// Populate `self.normals` with one entry per vertex.
+    ///
+    /// When the mesh is indexed (vertices were welded via `add_vertex_indexed`, so a
+    /// vertex can be shared by several triangles), normals are smooth: each triangle's
+    /// unnormalized cross product (area-weighted) is accumulated into its three
+    /// vertices, then every vertex normal is renormalized. An unindexed mesh has no
+    /// shared vertices for that accumulation to smooth over, so it falls back to the
+    /// analytic sphere normal instead: the vertex position normalized around the
+    /// sphere's center at the origin.
+    pub fn compute_normals(&mut self) {
+        if self.vertex_cache.is_empty() {
+            self.normals = self.vertices.iter().map(|&v| normalize(v)).collect();
+            return;
+        }
+
+        let mut normals = vec![[0.0f32; 3]; self.vertices.len()];
+
+        for triangle in self.indices.chunks_exact(3) {
+            let (a, b, c) = (triangle[0], triangle[1], triangle[2]);
+            let (pa, pb, pc) = (self.vertices[a], self.vertices[b], self.vertices[c]);
+
+            let face_normal = cross(sub(pb, pa), sub(pc, pa));
+            for &idx in &[a, b, c] {
+                normals[idx] = add(normals[idx], face_normal);
+            }
+        }
+
+        for (normal, &vertex) in normals.iter_mut().zip(&self.vertices) {
+            let len = (normal[0] * normal[0] + normal[1] * normal[1] + normal[2] * normal[2]).sqrt();
+            *normal = if len > f32::EPSILON {
+                [normal[0] / len, normal[1] / len, normal[2] / len]
+            } else {
+                normalize(vertex)
+            };
+        }
+
+        self.normals = normals;
+    }
+}
+
+fn sub(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn add(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [a[0] + b[0], a[1] + b[1], a[2] + b[2]]
+}
+
+fn cross(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+fn normalize(v: [f32; 3]) -> [f32; 3] {
+    let len = (v[0] * v[0] + v[1] * v[1] + v[2] * v[2]).sqrt();
+    if len > f32::EPSILON {
+        [v[0] / len, v[1] / len, v[2] / len]
+    } else {
+        [0.0, 0.0, 0.0]
+    }
 }
 
 /// Mesh statistics
@@ -46,11 +136,66 @@ pub struct MeshStats {
 /// Triangulate a pentagon using fan triangulation from center
 pub fn triangulate_pentagon(center_idx: usize, boundary_indices: &[usize]) -> Vec<[usize; 3]> {
     let mut triangles = Vec::new();
-    
+
     for i in 0..boundary_indices.len() {
         let next = (i + 1) % boundary_indices.len();
         triangles.push([center_idx, boundary_indices[i], boundary_indices[next]]);
     }
-    
+
     triangles
-} 
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn add_vertex_indexed_dedups_by_key() {
+        let mut mesh = Mesh::new();
+
+        let a = mesh.add_vertex_indexed(42, [1.0, 2.0, 3.0], [0.0, 0.0]);
+        let b = mesh.add_vertex_indexed(42, [9.0, 9.0, 9.0], [1.0, 1.0]);
+        let c = mesh.add_vertex_indexed(7, [4.0, 5.0, 6.0], [0.0, 0.0]);
+
+        assert_eq!(a, b, "same key must return the already-cached vertex index");
+        assert_ne!(a, c, "different key must add a new vertex");
+        assert_eq!(mesh.vertices.len(), 2, "the re-requested key must not push a duplicate vertex");
+        assert_eq!(mesh.vertices[a], [1.0, 2.0, 3.0], "the cached position must be the first one added, not the second");
+    }
+
+    #[test]
+    fn compute_normals_uses_analytic_sphere_normal_when_unindexed() {
+        let mut mesh = Mesh::new();
+        let a = mesh.add_vertex([2.0, 0.0, 0.0], [0.0, 0.0]);
+        let b = mesh.add_vertex([0.0, 2.0, 0.0], [0.0, 0.0]);
+        let c = mesh.add_vertex([0.0, 0.0, 2.0], [0.0, 0.0]);
+        mesh.add_triangle([a, b, c]);
+
+        mesh.compute_normals();
+
+        assert_eq!(mesh.normals[a], normalize([2.0, 0.0, 0.0]));
+        assert_eq!(mesh.normals[b], normalize([0.0, 2.0, 0.0]));
+        assert_eq!(mesh.normals[c], normalize([0.0, 0.0, 2.0]));
+    }
+
+    #[test]
+    fn compute_normals_smooths_across_shared_vertices_when_indexed() {
+        let mut mesh = Mesh::new();
+        // Two flat triangles sharing the edge (shared0, shared1), folded at a right
+        // angle, so the shared vertices' smoothed normal differs from either face normal.
+        let shared0 = mesh.add_vertex_indexed(0, [0.0, 0.0, 0.0], [0.0, 0.0]);
+        let shared1 = mesh.add_vertex_indexed(1, [0.0, 1.0, 0.0], [0.0, 0.0]);
+        let tip_a = mesh.add_vertex_indexed(2, [1.0, 0.0, 0.0], [0.0, 0.0]);
+        let tip_b = mesh.add_vertex_indexed(3, [0.0, 0.0, 1.0], [0.0, 0.0]);
+        mesh.add_triangle([shared0, shared1, tip_a]);
+        mesh.add_triangle([shared0, tip_b, shared1]);
+
+        mesh.compute_normals();
+
+        let face_a = normalize(cross(sub([0.0, 1.0, 0.0], [0.0, 0.0, 0.0]), sub([1.0, 0.0, 0.0], [0.0, 0.0, 0.0])));
+        assert_ne!(mesh.normals[shared0], face_a, "a shared vertex must be smoothed, not equal to just one face's normal");
+
+        let len = (mesh.normals[shared0][0].powi(2) + mesh.normals[shared0][1].powi(2) + mesh.normals[shared0][2].powi(2)).sqrt();
+        assert!((len - 1.0).abs() < 1e-5, "smoothed normals must be renormalized to unit length");
+    }
+}
\ No newline at end of file
@@ -0,0 +1,270 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::mesh::Mesh;
+
+/// Default vertex cap per meshlet, matching common GPU meshlet budgets.
+pub const DEFAULT_MAX_MESHLET_VERTICES: usize = 124;
+/// Default triangle cap per meshlet.
+pub const DEFAULT_MAX_MESHLET_TRIANGLES: usize = 64;
+
+/// Bounding sphere used for meshlet-level frustum/occlusion culling.
+#[derive(Debug, Clone, Copy)]
+pub struct BoundingSphere {
+    pub center: [f32; 3],
+    pub radius: f32,
+}
+
+/// A GPU-friendly cluster of a chunk `Mesh`: a local vertex remap table plus local
+/// (8-bit) index triples, bounded by `max_vertices`/`max_triangles`.
+#[derive(Debug)]
+pub struct Meshlet {
+    /// Local vertex index -> index into the source mesh's `vertices`/`uvs0`.
+    pub vertex_remap: Vec<usize>,
+    /// Triangles as local vertex indices (into `vertex_remap`), 3 per triangle.
+    pub local_indices: Vec<[u8; 3]>,
+    pub bounds: BoundingSphere,
+}
+
+fn edge_key(a: usize, b: usize) -> (usize, usize) {
+    if a < b {
+        (a, b)
+    } else {
+        (b, a)
+    }
+}
+
+/// Build the triangle dual graph: for each triangle, the neighboring triangles that
+/// share exactly one edge (two vertices) with it.
+fn build_dual_graph(triangles: &[[usize; 3]]) -> Vec<Vec<usize>> {
+    let mut edge_to_tris: HashMap<(usize, usize), Vec<usize>> = HashMap::new();
+    for (ti, tri) in triangles.iter().enumerate() {
+        for i in 0..3 {
+            let key = edge_key(tri[i], tri[(i + 1) % 3]);
+            edge_to_tris.entry(key).or_default().push(ti);
+        }
+    }
+
+    let mut adjacency = vec![Vec::new(); triangles.len()];
+    for tris in edge_to_tris.values() {
+        if tris.len() == 2 {
+            adjacency[tris[0]].push(tris[1]);
+            adjacency[tris[1]].push(tris[0]);
+        }
+    }
+    adjacency
+}
+
+fn bounding_sphere(vertex_remap: &[usize], mesh: &Mesh) -> BoundingSphere {
+    let mut center = [0.0f32; 3];
+    for &v in vertex_remap {
+        let p = mesh.vertices[v];
+        center[0] += p[0];
+        center[1] += p[1];
+        center[2] += p[2];
+    }
+    let count = vertex_remap.len().max(1) as f32;
+    center = [center[0] / count, center[1] / count, center[2] / count];
+
+    let mut radius = 0.0f32;
+    for &v in vertex_remap {
+        let p = mesh.vertices[v];
+        let dist = ((p[0] - center[0]).powi(2) + (p[1] - center[1]).powi(2) + (p[2] - center[2]).powi(2)).sqrt();
+        radius = radius.max(dist);
+    }
+
+    BoundingSphere { center, radius }
+}
+
+fn build_meshlet(cluster_tris: &[usize], triangles: &[[usize; 3]], mesh: &Mesh) -> Meshlet {
+    let mut vertex_remap = Vec::new();
+    let mut local_id: HashMap<usize, u8> = HashMap::new();
+    let mut local_indices = Vec::with_capacity(cluster_tris.len());
+
+    for &ti in cluster_tris {
+        let tri = triangles[ti];
+        let mut local_tri = [0u8; 3];
+        for (slot, &v) in local_tri.iter_mut().zip(tri.iter()) {
+            *slot = *local_id.entry(v).or_insert_with(|| {
+                let id = vertex_remap.len() as u8;
+                vertex_remap.push(v);
+                id
+            });
+        }
+        local_indices.push(local_tri);
+    }
+
+    let bounds = bounding_sphere(&vertex_remap, mesh);
+    Meshlet { vertex_remap, local_indices, bounds }
+}
+
+/// Partition `mesh` into meshlets of at most `max_vertices` vertices and
+/// `max_triangles` triangles each.
+///
+/// Builds the triangle dual graph (nodes = triangles, edges = shared mesh edges) and
+/// greedily region-grows clusters via BFS: each cluster seeds from a low-degree,
+/// not-yet-claimed triangle and accretes the unvisited neighbor that adds the fewest
+/// new vertices, skipping any that would exceed the vertex cap, until the frontier is
+/// empty or the triangle cap is reached. This approximates a METIS-style
+/// min-edge-cut partition without pulling in a full graph-partitioning dependency.
+///
+/// Errors if `max_vertices` exceeds 256: a meshlet's local indices are packed into
+/// `u8`, so a larger cap would silently wrap and corrupt the emitted indices.
+pub fn build_meshlets(mesh: &Mesh, max_vertices: usize, max_triangles: usize) -> Result<Vec<Meshlet>, Box<dyn std::error::Error>> {
+    if max_vertices > 256 {
+        return Err(format!(
+            "max_vertices ({}) exceeds 256: meshlet local indices are packed into u8",
+            max_vertices
+        ).into());
+    }
+
+    let triangles: Vec<[usize; 3]> = mesh.indices.chunks(3).map(|c| [c[0], c[1], c[2]]).collect();
+    if triangles.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let adjacency = build_dual_graph(&triangles);
+
+    // Seed clusters from low-degree triangles first so sparsely connected regions
+    // (e.g. a lone fan at the edge of a chunk) aren't left stranded until last.
+    let mut order: Vec<usize> = (0..triangles.len()).collect();
+    order.sort_by_key(|&ti| adjacency[ti].len());
+
+    let mut visited = vec![false; triangles.len()];
+    let mut meshlets = Vec::new();
+
+    for &seed in &order {
+        if visited[seed] {
+            continue;
+        }
+
+        let mut cluster_tris = Vec::new();
+        let mut cluster_vertices: HashSet<usize> = HashSet::new();
+        let mut frontier: Vec<usize> = vec![seed];
+        let mut in_frontier: HashSet<usize> = HashSet::from([seed]);
+
+        while !frontier.is_empty() && cluster_tris.len() < max_triangles {
+            let (pick_pos, &ti) = frontier
+                .iter()
+                .enumerate()
+                .min_by_key(|&(_, &ti)| {
+                    triangles[ti].iter().filter(|v| !cluster_vertices.contains(v)).count()
+                })
+                .expect("frontier is non-empty");
+
+            frontier.remove(pick_pos);
+            in_frontier.remove(&ti);
+
+            if visited[ti] {
+                continue;
+            }
+
+            let new_vertex_count = triangles[ti].iter().filter(|v| !cluster_vertices.contains(v)).count();
+            if cluster_vertices.len() + new_vertex_count > max_vertices {
+                continue; // doesn't fit; a later cluster will pick it up as its own seed
+            }
+
+            visited[ti] = true;
+            cluster_tris.push(ti);
+            cluster_vertices.extend(triangles[ti]);
+
+            for &n in &adjacency[ti] {
+                if !visited[n] && in_frontier.insert(n) {
+                    frontier.push(n);
+                }
+            }
+        }
+
+        meshlets.push(build_meshlet(&cluster_tris, &triangles, mesh));
+    }
+
+    Ok(meshlets)
+}
+
+/// Partition `mesh` using the recommended default vertex/triangle caps.
+pub fn build_default_meshlets(mesh: &Mesh) -> Result<Vec<Meshlet>, Box<dyn std::error::Error>> {
+    build_meshlets(mesh, DEFAULT_MAX_MESHLET_VERTICES, DEFAULT_MAX_MESHLET_TRIANGLES)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// An `n`x`n` grid of vertices on the XY plane, triangulated into `(n-1)^2` quads
+    /// (two triangles each), large enough to need more than one meshlet under small caps.
+    fn grid_mesh(n: usize) -> Mesh {
+        let mut mesh = Mesh::new();
+        for y in 0..n {
+            for x in 0..n {
+                mesh.add_vertex([x as f32, y as f32, 0.0], [0.0, 0.0]);
+            }
+        }
+        for y in 0..n - 1 {
+            for x in 0..n - 1 {
+                let i0 = y * n + x;
+                let i1 = i0 + 1;
+                let i2 = i0 + n + 1;
+                let i3 = i0 + n;
+                mesh.add_triangle([i0, i1, i2]);
+                mesh.add_triangle([i0, i2, i3]);
+            }
+        }
+        mesh
+    }
+
+    #[test]
+    fn build_meshlets_rejects_vertex_cap_above_u8_range() {
+        let mesh = grid_mesh(2);
+        let result = build_meshlets(&mesh, 257, 64);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn build_meshlets_respects_vertex_and_triangle_caps() {
+        let mesh = grid_mesh(6); // 5x5 quads = 50 triangles
+        let max_vertices = 8;
+        let max_triangles = 6;
+        let meshlets = build_meshlets(&mesh, max_vertices, max_triangles).unwrap();
+
+        assert!(!meshlets.is_empty());
+        for meshlet in &meshlets {
+            assert!(meshlet.vertex_remap.len() <= max_vertices);
+            assert!(meshlet.local_indices.len() <= max_triangles);
+
+            // Every local index must resolve to a vertex actually in this meshlet's remap.
+            for tri in &meshlet.local_indices {
+                for &local in tri {
+                    assert!((local as usize) < meshlet.vertex_remap.len());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn build_meshlets_partition_reconstructs_every_source_triangle_exactly_once() {
+        let mesh = grid_mesh(5); // 4x4 quads = 32 triangles
+        let meshlets = build_meshlets(&mesh, 10, 8).unwrap();
+
+        let source_triangles: Vec<[usize; 3]> = mesh.indices.chunks(3).map(|c| [c[0], c[1], c[2]]).collect();
+        let mut source_set: Vec<HashSet<usize>> = source_triangles.iter().map(|t| t.iter().copied().collect()).collect();
+
+        let mut reconstructed_count = 0;
+        for meshlet in &meshlets {
+            for tri in &meshlet.local_indices {
+                let global: HashSet<usize> = tri.iter().map(|&local| meshlet.vertex_remap[local as usize]).collect();
+                let pos = source_set.iter().position(|t| *t == global).expect("every meshlet triangle must be a source triangle");
+                source_set.remove(pos);
+                reconstructed_count += 1;
+            }
+        }
+
+        assert_eq!(reconstructed_count, source_triangles.len());
+        assert!(source_set.is_empty(), "every source triangle must be covered exactly once");
+    }
+
+    #[test]
+    fn build_meshlets_on_empty_mesh_returns_no_meshlets() {
+        let mesh = Mesh::new();
+        let meshlets = build_meshlets(&mesh, DEFAULT_MAX_MESHLET_VERTICES, DEFAULT_MAX_MESHLET_TRIANGLES).unwrap();
+        assert!(meshlets.is_empty());
+    }
+}
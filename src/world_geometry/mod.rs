@@ -2,8 +2,16 @@ pub mod geometry;
 pub mod mesh;
 pub mod world_gen;
 pub mod export;
+pub mod simplify;
+pub mod meshlet;
+pub mod validate;
+pub mod color;
 
 pub use geometry::*;
 pub use mesh::*;
-pub use world_gen::*; 
-pub use export::*;
\ No newline at end of file
+pub use world_gen::*;
+pub use export::*;
+pub use simplify::*;
+pub use meshlet::*;
+pub use validate::*;
+pub use color::*;
\ No newline at end of file
@@ -0,0 +1,516 @@
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+use crate::mesh::Mesh;
+
+/// Weight applied to the penalty quadric added along open chunk borders, relative to
+/// the regular face quadrics, so border vertices resist being pulled off the border
+/// as chunks are independently simplified.
+const BOUNDARY_WEIGHT: f64 = 1000.0;
+
+/// Symmetric 4x4 quadric error matrix (Garland-Heckbert), stored as its 10 unique
+/// upper-triangular entries: `[q00,q01,q02,q03, q11,q12,q13, q22,q23, q33]`.
+#[derive(Clone, Copy, Default)]
+struct Quadric([f64; 10]);
+
+impl Quadric {
+    /// Build `Kp = p * p^T` for a plane `p = [a, b, c, d]` with unit normal `[a, b, c]`.
+    fn from_plane(normal: [f64; 3], d: f64) -> Self {
+        let [a, b, c] = normal;
+        Quadric([
+            a * a, a * b, a * c, a * d,
+                   b * b, b * c, b * d,
+                          c * c, c * d,
+                                 d * d,
+        ])
+    }
+
+    fn add(&self, other: &Quadric) -> Quadric {
+        let mut out = [0.0; 10];
+        for i in 0..10 {
+            out[i] = self.0[i] + other.0[i];
+        }
+        Quadric(out)
+    }
+
+    fn scaled(&self, factor: f64) -> Quadric {
+        Quadric(self.0.map(|x| x * factor))
+    }
+
+    /// `v^T Q v` for the homogeneous point `v = [x, y, z, 1]`.
+    fn error_at(&self, v: [f64; 3]) -> f64 {
+        let [x, y, z] = v;
+        let q = self.0;
+        q[0] * x * x + 2.0 * q[1] * x * y + 2.0 * q[2] * x * z + 2.0 * q[3] * x
+            + q[4] * y * y + 2.0 * q[5] * y * z + 2.0 * q[6] * y
+            + q[7] * z * z + 2.0 * q[8] * z
+            + q[9]
+    }
+
+    /// Position minimizing `error_at`, solving the 3x3 linear system formed by the
+    /// upper-left of `Q`. Falls back to `fallback` (the edge midpoint) when that
+    /// system is singular, e.g. when all incident faces are coplanar.
+    fn optimal_position(&self, fallback: [f64; 3]) -> [f64; 3] {
+        let q = self.0;
+        let a = [
+            [q[0], q[1], q[2]],
+            [q[1], q[4], q[5]],
+            [q[2], q[5], q[7]],
+        ];
+        let b = [-q[3], -q[6], -q[8]];
+
+        let det = determinant3(a);
+        if det.abs() < 1e-9 {
+            return fallback;
+        }
+
+        let solve_column = |col: usize| -> f64 {
+            let mut m = a;
+            m[0][col] = b[0];
+            m[1][col] = b[1];
+            m[2][col] = b[2];
+            determinant3(m) / det
+        };
+
+        [solve_column(0), solve_column(1), solve_column(2)]
+    }
+}
+
+fn determinant3(m: [[f64; 3]; 3]) -> f64 {
+    m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+        - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+        + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0])
+}
+
+fn sub(a: [f64; 3], b: [f64; 3]) -> [f64; 3] {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn add_vec(a: [f64; 3], b: [f64; 3]) -> [f64; 3] {
+    [a[0] + b[0], a[1] + b[1], a[2] + b[2]]
+}
+
+fn scale_vec(a: [f64; 3], s: f64) -> [f64; 3] {
+    [a[0] * s, a[1] * s, a[2] * s]
+}
+
+fn cross(a: [f64; 3], b: [f64; 3]) -> [f64; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+fn dot(a: [f64; 3], b: [f64; 3]) -> f64 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn length(a: [f64; 3]) -> f64 {
+    dot(a, a).sqrt()
+}
+
+/// Unit normal and plane distance `d` (`normal . p + d = 0`) for a triangle, or `None`
+/// if the triangle is degenerate (zero area).
+fn triangle_plane(tri: [usize; 3], positions: &[[f64; 3]]) -> Option<([f64; 3], f64)> {
+    let p0 = positions[tri[0]];
+    let p1 = positions[tri[1]];
+    let p2 = positions[tri[2]];
+    let normal = cross(sub(p1, p0), sub(p2, p0));
+    let len = length(normal);
+    if len < 1e-12 {
+        return None;
+    }
+    let normal = scale_vec(normal, 1.0 / len);
+    let d = -dot(normal, p0);
+    Some((normal, d))
+}
+
+/// An edge collapse candidate queued for processing, ordered so the lowest-error
+/// collapse is popped first from a (max-heap) `BinaryHeap`.
+struct EdgeCost {
+    cost: f64,
+    v1: usize,
+    v2: usize,
+    target: [f64; 3],
+    /// Vertex generation counters captured at push time; if either vertex has since
+    /// been touched by another collapse this entry is stale and must be discarded.
+    version: (u32, u32),
+}
+
+impl PartialEq for EdgeCost {
+    fn eq(&self, other: &Self) -> bool {
+        self.cost == other.cost
+    }
+}
+impl Eq for EdgeCost {}
+impl PartialOrd for EdgeCost {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl Ord for EdgeCost {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Reversed so the *lowest* cost sorts first out of the max-heap.
+        other.cost.partial_cmp(&self.cost).unwrap_or(Ordering::Equal)
+    }
+}
+
+fn compute_edge_cost(
+    v1: usize,
+    v2: usize,
+    quadrics: &[Quadric],
+    positions: &[[f64; 3]],
+    vertex_gen: &[u32],
+) -> EdgeCost {
+    let q = quadrics[v1].add(&quadrics[v2]);
+    let midpoint = scale_vec(add_vec(positions[v1], positions[v2]), 0.5);
+    let target = q.optimal_position(midpoint);
+    let cost = q.error_at(target);
+    EdgeCost {
+        cost,
+        v1,
+        v2,
+        target,
+        version: (vertex_gen[v1], vertex_gen[v2]),
+    }
+}
+
+fn edge_key(a: usize, b: usize) -> (usize, usize) {
+    if a < b {
+        (a, b)
+    } else {
+        (b, a)
+    }
+}
+
+/// Simplify `mesh` down to roughly `target_ratio` of its original triangle count using
+/// quadric error metric (QEM) edge collapse. `target_ratio` is clamped to `[0.0, 1.0]`.
+///
+/// Collapses are rejected when they would flip a triangle's normal or pinch the mesh
+/// into a non-manifold vertex/edge. Boundary edges are not rejected outright: a
+/// heavily weighted penalty quadric along them makes collapses that pull a border
+/// vertex off the border costly. Collapses along a border are not penalized.
+pub fn simplify_mesh(mesh: &Mesh, target_ratio: f32) -> Mesh {
+    let target_ratio = target_ratio.clamp(0.0, 1.0);
+    let triangle_total = mesh.indices.len() / 3;
+    let target_triangles = ((triangle_total as f32 * target_ratio).round() as usize).max(1);
+
+    let mut positions: Vec<[f64; 3]> = mesh
+        .vertices
+        .iter()
+        .map(|v| [v[0] as f64, v[1] as f64, v[2] as f64])
+        .collect();
+    let uvs = mesh.uvs0.clone();
+
+    let mut triangles: Vec<[usize; 3]> = mesh
+        .indices
+        .chunks(3)
+        .map(|c| [c[0], c[1], c[2]])
+        .collect();
+    let mut alive_tri = vec![true; triangles.len()];
+    let mut alive_vertex = vec![true; positions.len()];
+    let mut vertex_gen = vec![0u32; positions.len()];
+
+    let mut vertex_tris: Vec<HashSet<usize>> = vec![HashSet::new(); positions.len()];
+    for (ti, tri) in triangles.iter().enumerate() {
+        for &v in tri {
+            vertex_tris[v].insert(ti);
+        }
+    }
+
+    let mut quadrics = vec![Quadric::default(); positions.len()];
+    for tri in &triangles {
+        if let Some((normal, d)) = triangle_plane(*tri, &positions) {
+            let q = Quadric::from_plane(normal, d);
+            for &v in tri {
+                quadrics[v] = quadrics[v].add(&q);
+            }
+        }
+    }
+
+    // Edges used by exactly one triangle are chunk/mesh boundaries; add a large
+    // penalty quadric for the plane through each such edge, perpendicular to its face,
+    // so collapses that move a border vertex off that plane are costly.
+    let mut edge_faces: HashMap<(usize, usize), u32> = HashMap::new();
+    for (ti, tri) in triangles.iter().enumerate() {
+        for i in 0..3 {
+            let key = edge_key(tri[i], tri[(i + 1) % 3]);
+            *edge_faces.entry(key).or_insert(0) += 1;
+            let _ = ti;
+        }
+    }
+    for tri in &triangles {
+        let Some((face_normal, _)) = triangle_plane(*tri, &positions) else {
+            continue;
+        };
+        for i in 0..3 {
+            let a = tri[i];
+            let b = tri[(i + 1) % 3];
+            if edge_faces[&edge_key(a, b)] != 1 {
+                continue;
+            }
+            let (pa, pb) = (positions[a], positions[b]);
+            let edge_len = length(sub(pb, pa));
+            if edge_len < 1e-12 {
+                continue;
+            }
+            let edge_dir = scale_vec(sub(pb, pa), 1.0 / edge_len);
+            let plane_normal = cross(edge_dir, face_normal);
+            let plane_len = length(plane_normal);
+            if plane_len < 1e-12 {
+                continue;
+            }
+            let plane_normal = scale_vec(plane_normal, 1.0 / plane_len);
+            let d = -dot(plane_normal, pa);
+            let penalty = Quadric::from_plane(plane_normal, d).scaled(BOUNDARY_WEIGHT * edge_len * edge_len);
+            quadrics[a] = quadrics[a].add(&penalty);
+            quadrics[b] = quadrics[b].add(&penalty);
+        }
+    }
+
+    let mut heap: BinaryHeap<EdgeCost> = BinaryHeap::new();
+    let mut seeded: HashSet<(usize, usize)> = HashSet::new();
+    for tri in &triangles {
+        for i in 0..3 {
+            let key = edge_key(tri[i], tri[(i + 1) % 3]);
+            if seeded.insert(key) {
+                heap.push(compute_edge_cost(key.0, key.1, &quadrics, &positions, &vertex_gen));
+            }
+        }
+    }
+
+    let mut triangle_count = triangle_total;
+
+    while triangle_count > target_triangles {
+        let Some(entry) = heap.pop() else { break };
+        let (v1, v2) = (entry.v1, entry.v2);
+
+        if !alive_vertex[v1] || !alive_vertex[v2] {
+            continue;
+        }
+        if entry.version != (vertex_gen[v1], vertex_gen[v2]) {
+            continue;
+        }
+
+        // Reject collapses that violate the edge link condition: the one-ring
+        // neighbors v1 and v2 have in common must be exactly the apex vertices of the
+        // triangle(s) shared by the edge (v1, v2) itself. Any other common neighbor
+        // means v1 and v2 are also connected through some other path, so merging them
+        // would pinch the mesh into a non-manifold bowtie vertex/edge there.
+        let one_ring = |v: usize| -> HashSet<usize> {
+            vertex_tris[v]
+                .iter()
+                .filter(|&&ti| alive_tri[ti])
+                .flat_map(|&ti| triangles[ti])
+                .filter(|&w| w != v)
+                .collect()
+        };
+        let shared_apexes: HashSet<usize> = vertex_tris[v1]
+            .iter()
+            .filter(|&&ti| alive_tri[ti] && triangles[ti].contains(&v2))
+            .flat_map(|&ti| triangles[ti])
+            .filter(|&w| w != v1 && w != v2)
+            .collect();
+        let common_neighbors: HashSet<usize> = one_ring(v1).intersection(&one_ring(v2)).copied().collect();
+
+        if common_neighbors != shared_apexes {
+            continue;
+        }
+
+        // Reject collapses that would flip the normal of any triangle left standing
+        // after v1 and v2 are both moved to the collapse target.
+        let flips_normal = |moved: usize, stationary_owner: usize| -> bool {
+            vertex_tris[moved].iter().any(|&ti| {
+                if !alive_tri[ti] {
+                    return false;
+                }
+                let tri = triangles[ti];
+                if tri.contains(&stationary_owner) {
+                    return false; // shared face, collapses to zero area instead
+                }
+                let Some((old_normal, _)) = triangle_plane(tri, &positions) else {
+                    return false;
+                };
+                let pos_of = |v: usize| if v == moved { entry.target } else { positions[v] };
+                let new_normal = cross(sub(pos_of(tri[1]), pos_of(tri[0])), sub(pos_of(tri[2]), pos_of(tri[0])));
+                dot(new_normal, old_normal) < 0.0
+            })
+        };
+
+        if flips_normal(v2, v1) || flips_normal(v1, v2) {
+            continue;
+        }
+
+        // Perform the collapse: v2 merges into v1 at the optimal position.
+        positions[v1] = entry.target;
+        quadrics[v1] = quadrics[v1].add(&quadrics[v2]);
+        alive_vertex[v2] = false;
+        vertex_gen[v1] += 1;
+        vertex_gen[v2] += 1;
+
+        for ti in vertex_tris[v2].clone() {
+            if !alive_tri[ti] {
+                continue;
+            }
+            if triangles[ti].contains(&v1) {
+                alive_tri[ti] = false;
+                triangle_count -= 1;
+                continue;
+            }
+            for slot in triangles[ti].iter_mut() {
+                if *slot == v2 {
+                    *slot = v1;
+                }
+            }
+            vertex_tris[v1].insert(ti);
+        }
+        vertex_tris[v2].clear();
+
+        let neighbors: HashSet<usize> = vertex_tris[v1]
+            .iter()
+            .filter(|&&ti| alive_tri[ti])
+            .flat_map(|&ti| triangles[ti])
+            .filter(|&v| v != v1)
+            .collect();
+        for n in neighbors {
+            if alive_vertex[n] {
+                heap.push(compute_edge_cost(v1, n, &quadrics, &positions, &vertex_gen));
+            }
+        }
+    }
+
+    let mut remap = vec![usize::MAX; positions.len()];
+    let mut out_vertices = Vec::new();
+    let mut out_uvs = Vec::new();
+    let mut out_indices = Vec::new();
+
+    for (ti, tri) in triangles.iter().enumerate() {
+        if !alive_tri[ti] {
+            continue;
+        }
+        for &v in tri {
+            if remap[v] == usize::MAX {
+                remap[v] = out_vertices.len();
+                out_vertices.push([positions[v][0] as f32, positions[v][1] as f32, positions[v][2] as f32]);
+                out_uvs.push(uvs[v]);
+            }
+        }
+        out_indices.extend_from_slice(&[remap[tri[0]], remap[tri[1]], remap[tri[2]]]);
+    }
+
+    let mut simplified = Mesh::new();
+    simplified.vertices = out_vertices;
+    simplified.uvs0 = out_uvs;
+    simplified.indices = out_indices;
+    simplified
+}
+
+/// Generate a chain of decimated copies of `mesh`, one per entry in `ratios` (e.g.
+/// `[1.0, 0.5, 0.25, 0.125]` for LOD0..LOD3). Each ratio simplifies independently from
+/// the original mesh rather than chaining off the previous LOD, so quality doesn't
+/// compound across the chain.
+pub fn generate_lod_chain(mesh: &Mesh, ratios: &[f32]) -> Vec<Mesh> {
+    ratios.iter().map(|&ratio| simplify_mesh(mesh, ratio)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// An `n`x`n` grid of vertices on the XY plane, triangulated into `(n-1)^2` quads
+    /// (two triangles each), for exercising simplification on more than one quad.
+    fn grid_mesh(n: usize) -> Mesh {
+        let mut mesh = Mesh::new();
+        for y in 0..n {
+            for x in 0..n {
+                mesh.add_vertex([x as f32, y as f32, 0.0], [0.0, 0.0]);
+            }
+        }
+        for y in 0..n - 1 {
+            for x in 0..n - 1 {
+                let i0 = y * n + x;
+                let i1 = i0 + 1;
+                let i2 = i0 + n + 1;
+                let i3 = i0 + n;
+                mesh.add_triangle([i0, i1, i2]);
+                mesh.add_triangle([i0, i2, i3]);
+            }
+        }
+        mesh
+    }
+
+    fn triangle_area(a: [f32; 3], b: [f32; 3], c: [f32; 3]) -> f32 {
+        let ab = [b[0] - a[0], b[1] - a[1], b[2] - a[2]];
+        let ac = [c[0] - a[0], c[1] - a[1], c[2] - a[2]];
+        let cross = [
+            ab[1] * ac[2] - ab[2] * ac[1],
+            ab[2] * ac[0] - ab[0] * ac[2],
+            ab[0] * ac[1] - ab[1] * ac[0],
+        ];
+        0.5 * (cross[0] * cross[0] + cross[1] * cross[1] + cross[2] * cross[2]).sqrt()
+    }
+
+    #[test]
+    fn simplify_mesh_ratio_one_is_a_topology_noop() {
+        let mesh = grid_mesh(4);
+        let simplified = simplify_mesh(&mesh, 1.0);
+
+        // target_ratio 1.0 means target_triangles == the original count, so the
+        // collapse loop never runs and every vertex/triangle survives untouched.
+        assert_eq!(simplified.indices.len(), mesh.indices.len());
+        assert_eq!(simplified.vertices.len(), mesh.vertices.len());
+    }
+
+    #[test]
+    fn simplify_mesh_reduces_triangle_count() {
+        let mesh = grid_mesh(4); // 3x3 quads = 18 triangles
+        let original_triangles = mesh.indices.len() / 3;
+        let simplified = simplify_mesh(&mesh, 0.5);
+        let simplified_triangles = simplified.indices.len() / 3;
+
+        assert!(simplified_triangles < original_triangles);
+        assert!(simplified_triangles > 0);
+    }
+
+    #[test]
+    fn simplify_mesh_never_leaves_degenerate_triangles() {
+        let mesh = grid_mesh(5);
+        let simplified = simplify_mesh(&mesh, 0.25);
+
+        assert!(!simplified.indices.is_empty());
+        for tri in simplified.indices.chunks(3) {
+            let (a, b, c) = (tri[0], tri[1], tri[2]);
+            assert_ne!(a, b);
+            assert_ne!(b, c);
+            assert_ne!(a, c);
+            let area = triangle_area(simplified.vertices[a], simplified.vertices[b], simplified.vertices[c]);
+            assert!(area > 1e-6, "simplification left a zero-area triangle");
+        }
+    }
+
+    #[test]
+    fn simplify_mesh_clamps_out_of_range_ratios() {
+        let mesh = grid_mesh(4);
+
+        // target_ratio is clamped to [0.0, 1.0], so values outside that range must
+        // behave identically to their clamped endpoint.
+        let below = simplify_mesh(&mesh, -1.0);
+        let zero = simplify_mesh(&mesh, 0.0);
+        assert_eq!(below.indices.len(), zero.indices.len());
+
+        let above = simplify_mesh(&mesh, 2.0);
+        let one = simplify_mesh(&mesh, 1.0);
+        assert_eq!(above.indices.len(), one.indices.len());
+    }
+
+    #[test]
+    fn generate_lod_chain_produces_one_mesh_per_ratio_with_decreasing_detail() {
+        let mesh = grid_mesh(4);
+        let lods = generate_lod_chain(&mesh, &[1.0, 0.5, 0.25]);
+
+        assert_eq!(lods.len(), 3);
+        assert!(lods[0].indices.len() >= lods[1].indices.len());
+        assert!(lods[1].indices.len() >= lods[2].indices.len());
+    }
+}
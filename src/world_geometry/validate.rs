@@ -0,0 +1,301 @@
+use std::collections::HashMap;
+
+use crate::mesh::Mesh;
+
+/// Findings from a `validate` pass over a finished `Mesh`.
+#[derive(Debug, Default, Clone)]
+pub struct ValidationReport {
+    /// Indices into `Mesh::vertices` whose coordinates aren't finite (NaN/Inf).
+    pub non_finite_vertices: Vec<usize>,
+    /// Triangle indices (0-based, i.e. `indices[3*t..3*t+3]`) with zero area or a
+    /// repeated vertex index.
+    pub degenerate_triangles: Vec<usize>,
+    /// Triangle indices that reference a vertex index `>= vertices.len()`.
+    pub out_of_range_indices: Vec<usize>,
+    /// Vertex indices referenced by no triangle.
+    pub orphan_vertices: Vec<usize>,
+    /// `(kept, duplicate)` vertex index pairs that are coincident within an epsilon.
+    pub duplicate_vertices: Vec<(usize, usize)>,
+}
+
+impl ValidationReport {
+    /// `true` if no issues were found.
+    pub fn is_clean(&self) -> bool {
+        self.non_finite_vertices.is_empty()
+            && self.degenerate_triangles.is_empty()
+            && self.out_of_range_indices.is_empty()
+            && self.orphan_vertices.is_empty()
+            && self.duplicate_vertices.is_empty()
+    }
+}
+
+/// Counts of fixes applied by `repair`.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct RepairCounts {
+    pub degenerate_triangles_removed: usize,
+    pub orphan_vertices_dropped: usize,
+    pub vertices_welded: usize,
+}
+
+fn triangle_area(a: [f32; 3], b: [f32; 3], c: [f32; 3]) -> f32 {
+    let ab = [b[0] - a[0], b[1] - a[1], b[2] - a[2]];
+    let ac = [c[0] - a[0], c[1] - a[1], c[2] - a[2]];
+    let cross = [
+        ab[1] * ac[2] - ab[2] * ac[1],
+        ab[2] * ac[0] - ab[0] * ac[2],
+        ab[0] * ac[1] - ab[1] * ac[0],
+    ];
+    0.5 * (cross[0] * cross[0] + cross[1] * cross[1] + cross[2] * cross[2]).sqrt()
+}
+
+/// Snap a vertex onto an `epsilon`-sized grid; vertices that round to the same grid
+/// cell share a bucket. Two vertices closer than `epsilon` that straddle a cell
+/// boundary land in different buckets.
+fn quantize(v: [f32; 3], epsilon: f32) -> (i64, i64, i64) {
+    (
+        (v[0] / epsilon).round() as i64,
+        (v[1] / epsilon).round() as i64,
+        (v[2] / epsilon).round() as i64,
+    )
+}
+
+/// Walk a finished mesh and report non-finite vertices, degenerate triangles,
+/// out-of-range indices, orphan vertices, and near-duplicate coincident vertices
+/// (within `weld_epsilon`). Catches the malformed geometry `lat_lng_to_3d` can emit
+/// near the poles before it ships in an export.
+pub fn validate(mesh: &Mesh, weld_epsilon: f32) -> ValidationReport {
+    let mut report = ValidationReport::default();
+    let vertex_count = mesh.vertices.len();
+
+    for (i, v) in mesh.vertices.iter().enumerate() {
+        if !v.iter().all(|c| c.is_finite()) {
+            report.non_finite_vertices.push(i);
+        }
+    }
+
+    let mut referenced = vec![false; vertex_count];
+    for (ti, tri) in mesh.indices.chunks(3).enumerate() {
+        if tri.len() < 3 {
+            continue;
+        }
+        let (a, b, c) = (tri[0], tri[1], tri[2]);
+        if a >= vertex_count || b >= vertex_count || c >= vertex_count {
+            report.out_of_range_indices.push(ti);
+            continue;
+        }
+        referenced[a] = true;
+        referenced[b] = true;
+        referenced[c] = true;
+
+        if a == b || b == c || a == c {
+            report.degenerate_triangles.push(ti);
+            continue;
+        }
+        if triangle_area(mesh.vertices[a], mesh.vertices[b], mesh.vertices[c]) <= f32::EPSILON {
+            report.degenerate_triangles.push(ti);
+        }
+    }
+
+    for (i, &used) in referenced.iter().enumerate() {
+        if !used {
+            report.orphan_vertices.push(i);
+        }
+    }
+
+    let mut buckets: HashMap<(i64, i64, i64), usize> = HashMap::new();
+    for (i, &v) in mesh.vertices.iter().enumerate() {
+        let key = quantize(v, weld_epsilon);
+        if let Some(&canonical) = buckets.get(&key) {
+            report.duplicate_vertices.push((canonical, i));
+        } else {
+            buckets.insert(key, i);
+        }
+    }
+
+    report
+}
+
+/// Drop degenerate/out-of-range triangles from `mesh.indices` in place. Returns how
+/// many were removed. Split out of `repair` so it can be re-run after welding, since
+/// welding two distinct vertices of a triangle together can itself create a new
+/// degenerate triangle.
+fn remove_degenerate_triangles(mesh: &mut Mesh) -> usize {
+    let vertex_count = mesh.vertices.len();
+    let mut removed = 0;
+
+    let mut kept_indices = Vec::with_capacity(mesh.indices.len());
+    for tri in mesh.indices.chunks(3) {
+        if tri.len() < 3 {
+            continue;
+        }
+        let (a, b, c) = (tri[0], tri[1], tri[2]);
+        let out_of_range = a >= vertex_count || b >= vertex_count || c >= vertex_count;
+        let degenerate = !out_of_range
+            && (a == b || b == c || a == c || triangle_area(mesh.vertices[a], mesh.vertices[b], mesh.vertices[c]) <= f32::EPSILON);
+
+        if out_of_range || degenerate {
+            removed += 1;
+            continue;
+        }
+        kept_indices.extend_from_slice(&[a, b, c]);
+    }
+    mesh.indices = kept_indices;
+
+    removed
+}
+
+/// Repair a mesh in place: drop degenerate/out-of-range triangles, optionally weld
+/// coincident vertices within `weld_epsilon` (re-checking for newly-degenerate
+/// triangles that weld produces), then drop orphan vertices and remap surviving
+/// indices. Returns counts of each fix applied.
+pub fn repair(mesh: &mut Mesh, weld_epsilon: Option<f32>) -> RepairCounts {
+    let mut counts = RepairCounts::default();
+
+    counts.degenerate_triangles_removed += remove_degenerate_triangles(mesh);
+
+    if let Some(epsilon) = weld_epsilon {
+        let mut buckets: HashMap<(i64, i64, i64), usize> = HashMap::new();
+        let mut remap: Vec<usize> = (0..mesh.vertices.len()).collect();
+        for (i, &v) in mesh.vertices.iter().enumerate() {
+            let key = quantize(v, epsilon);
+            match buckets.get(&key) {
+                Some(&canonical) => {
+                    remap[i] = canonical;
+                    counts.vertices_welded += 1;
+                }
+                None => {
+                    buckets.insert(key, i);
+                }
+            }
+        }
+        for index in mesh.indices.iter_mut() {
+            *index = remap[*index];
+        }
+
+        // A weld can collapse two of a triangle's distinct vertices onto the same
+        // canonical vertex, turning it degenerate; sweep again so that doesn't ship.
+        counts.degenerate_triangles_removed += remove_degenerate_triangles(mesh);
+    }
+
+    let mut referenced = vec![false; mesh.vertices.len()];
+    for &index in &mesh.indices {
+        referenced[index] = true;
+    }
+
+    let mut new_vertices = Vec::new();
+    let mut new_uvs = Vec::new();
+    // `colors`/`normals` are optional per-vertex arrays (empty until a color mode or
+    // `compute_normals` has been applied); only compact whichever are populated so
+    // they stay aligned with `vertices` through welding and orphan-dropping instead of
+    // silently drifting out of sync with it.
+    let has_colors = !mesh.colors.is_empty();
+    let mut new_colors = Vec::new();
+    let has_normals = !mesh.normals.is_empty();
+    let mut new_normals = Vec::new();
+    let mut compact_remap = vec![usize::MAX; mesh.vertices.len()];
+    for (i, &used) in referenced.iter().enumerate() {
+        if used {
+            compact_remap[i] = new_vertices.len();
+            new_vertices.push(mesh.vertices[i]);
+            new_uvs.push(mesh.uvs0[i]);
+            if has_colors {
+                new_colors.push(mesh.colors[i]);
+            }
+            if has_normals {
+                new_normals.push(mesh.normals[i]);
+            }
+        } else {
+            counts.orphan_vertices_dropped += 1;
+        }
+    }
+
+    for index in mesh.indices.iter_mut() {
+        *index = compact_remap[*index];
+    }
+    mesh.vertices = new_vertices;
+    mesh.uvs0 = new_uvs;
+    if has_colors {
+        mesh.colors = new_colors;
+    }
+    if has_normals {
+        mesh.normals = new_normals;
+    }
+
+    counts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn push_vertex(mesh: &mut Mesh, v: [f32; 3]) -> usize {
+        mesh.add_vertex(v, [0.0, 0.0])
+    }
+
+    #[test]
+    fn repair_rechecks_degeneracy_after_welding() {
+        let mut mesh = Mesh::new();
+        let epsilon = 1e-4;
+
+        let v0 = push_vertex(&mut mesh, [0.0, 0.0, 0.0]);
+        let v1 = push_vertex(&mut mesh, [1.0, 0.0, 0.0]);
+        let v2 = push_vertex(&mut mesh, [0.0, 1.0, 0.0]);
+        // Within `epsilon` of v1, so it welds onto v1.
+        let v3 = push_vertex(&mut mesh, [1.0, 0.0, 1e-5]);
+        // Never referenced by a triangle; should be dropped as an orphan.
+        let _v4 = push_vertex(&mut mesh, [5.0, 5.0, 5.0]);
+
+        // A valid triangle untouched by welding.
+        mesh.add_triangle([v0, v1, v2]);
+        // A valid triangle until v3 welds onto v1, after which it repeats a vertex.
+        mesh.add_triangle([v0, v1, v3]);
+
+        let counts = repair(&mut mesh, Some(epsilon));
+
+        assert_eq!(counts.vertices_welded, 1);
+        assert_eq!(counts.degenerate_triangles_removed, 1, "triangle degenerate only after welding must still be removed");
+        assert_eq!(counts.orphan_vertices_dropped, 2, "the welded-away and never-referenced vertices are both orphaned");
+        assert_eq!(mesh.vertices.len(), 3);
+        assert_eq!(mesh.indices, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn repair_keeps_colors_and_normals_aligned_with_vertices() {
+        let mut mesh = Mesh::new();
+        let v0 = push_vertex(&mut mesh, [0.0, 0.0, 0.0]);
+        let v1 = push_vertex(&mut mesh, [1.0, 0.0, 0.0]);
+        let v2 = push_vertex(&mut mesh, [0.0, 1.0, 0.0]);
+        let _orphan = push_vertex(&mut mesh, [5.0, 5.0, 5.0]);
+
+        mesh.colors = vec![[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0], [1.0, 1.0, 1.0]];
+        mesh.normals = vec![[0.0, 0.0, 1.0], [0.0, 0.0, 1.0], [0.0, 0.0, 1.0], [0.0, 1.0, 0.0]];
+        mesh.add_triangle([v0, v1, v2]);
+
+        let counts = repair(&mut mesh, None);
+
+        assert_eq!(counts.orphan_vertices_dropped, 1);
+        assert_eq!(mesh.colors, vec![[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]]);
+        assert_eq!(mesh.normals, vec![[0.0, 0.0, 1.0], [0.0, 0.0, 1.0], [0.0, 0.0, 1.0]]);
+        assert_eq!(mesh.colors.len(), mesh.vertices.len());
+        assert_eq!(mesh.normals.len(), mesh.vertices.len());
+    }
+
+    #[test]
+    fn validate_reports_orphan_and_duplicate_vertices() {
+        let mut mesh = Mesh::new();
+        let v0 = push_vertex(&mut mesh, [0.0, 0.0, 0.0]);
+        let v1 = push_vertex(&mut mesh, [1.0, 0.0, 0.0]);
+        let v2 = push_vertex(&mut mesh, [0.0, 1.0, 0.0]);
+        let _orphan = push_vertex(&mut mesh, [9.0, 9.0, 9.0]);
+        let _duplicate = push_vertex(&mut mesh, [0.0, 0.0, 1e-6]); // coincident with v0, also unreferenced
+
+        mesh.add_triangle([v0, v1, v2]);
+
+        let report = validate(&mesh, 1e-4);
+
+        assert_eq!(report.orphan_vertices, vec![3, 4]);
+        assert_eq!(report.duplicate_vertices, vec![(0, 4)]);
+        assert!(report.degenerate_triangles.is_empty());
+        assert!(!report.is_clean());
+    }
+}
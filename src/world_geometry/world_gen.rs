@@ -1,7 +1,15 @@
 use h3o::{CellIndex, LatLng};
 use crate::{mesh::{Mesh, triangulate_pentagon}, geometry::lat_lng_to_3d};
 use std::path::Path;
-use super::export::export_gltf;
+use super::color::{apply_color_mode, ColorMode};
+use super::export::{export_geojson, export_gltf, export_gltf_lods, export_gltf_meshlets};
+use super::meshlet::build_default_meshlets;
+use super::simplify::generate_lod_chain;
+use super::validate::{repair, validate};
+
+/// Vertices within this distance are considered coincident when welding during
+/// `repair`. Small relative to a typical chunk's sphere radius.
+const REPAIR_WELD_EPSILON: f32 = 1e-4;
 
 /// Statistics about H3 processing
 #[derive(Debug, Default)]
@@ -10,18 +18,29 @@ pub struct ProcessingStats {
     pub hexagon_count: usize,
     pub invalid_coords: usize,
     pub cells_processed: usize,
+    pub degenerate_triangles_removed: usize,
+    pub orphan_vertices_dropped: usize,
+    pub vertices_welded: usize,
 }
 
-/// Generate a world geometry mesh
-pub fn gen_world_geometry(
-    sphere_radius: f64,
-    resolution: u8,
-) -> Result<(Mesh, ProcessingStats), Box<dyn std::error::Error>> {
+/// Which on-disk format(s) `gen_world_chunks` emits for each chunk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ExportFormat {
+    /// Triangulated glTF mesh only.
+    Gltf,
+    /// True-boundary GeoJSON only.
+    #[value(name = "geojson")]
+    GeoJson,
+    /// Both a glTF mesh and a GeoJSON sidecar.
+    Both,
+}
+
+/// Collect every H3 cell at `resolution`, validating the resolution first.
+pub fn collect_cells_at_resolution(resolution: u8) -> Result<Vec<CellIndex>, Box<dyn std::error::Error>> {
     let res_enum = h3o::Resolution::try_from(resolution)
         .map_err(|_| format!("Invalid H3 resolution: {} (must be 0..=15)", resolution))?;
 
-    // Collect all cell indices for the requested resolution.
-    let cells: Vec<CellIndex> = if resolution == 0 {
+    let cells = if resolution == 0 {
         CellIndex::base_cells().collect()
     } else {
         // For higher resolutions, gather children of each base cell.
@@ -30,6 +49,16 @@ pub fn gen_world_geometry(
             .collect()
     };
 
+    Ok(cells)
+}
+
+/// Generate a world geometry mesh
+pub fn gen_world_geometry(
+    sphere_radius: f64,
+    resolution: u8,
+) -> Result<(Mesh, ProcessingStats), Box<dyn std::error::Error>> {
+    let cells = collect_cells_at_resolution(resolution)?;
+
     println!(
         "Generating geometry for {} cells at resolution {}",
         cells.len(), resolution
@@ -86,7 +115,7 @@ pub fn process_single_cell(
         return Err("Invalid center coordinates".into());
     }
     
-    let center_idx = mesh.add_vertex(center_3d, [1.0; 3]);
+    let center_idx = mesh.add_vertex(center_3d, [1.0, 1.0]);
     
     // Get the boundary vertices
     let vertex_indices: Vec<_> = cell.vertexes().collect();
@@ -95,16 +124,18 @@ pub fn process_single_cell(
     for (j, vertex_index) in vertex_indices.iter().enumerate() {
         let vertex_latlng = LatLng::from(*vertex_index);
         let vertex_3d = lat_lng_to_3d(vertex_latlng.lat(), vertex_latlng.lng(), sphere_radius);
-        
+
         // Check for invalid coordinates
         if !vertex_3d.iter().all(|&x| x.is_finite()) {
-            eprintln!("Invalid vertex coordinate for cell {} vertex {}: {:?} (lat: {}, lng: {})", 
+            eprintln!("Invalid vertex coordinate for cell {} vertex {}: {:?} (lat: {}, lng: {})",
                      cell_index, j, vertex_3d, vertex_latlng.lat(), vertex_latlng.lng());
             stats.invalid_coords += 1;
             continue;
         }
-        
-        let vertex_idx = mesh.add_vertex(vertex_3d, [1.0; 3]);
+
+        // The vertex's own H3 index is the same for every cell that touches this corner,
+        // so keying on it welds shared edges instead of emitting a duplicate vertex.
+        let vertex_idx = mesh.add_vertex_indexed(u64::from(*vertex_index), vertex_3d, [1.0, 1.0]);
         boundary_indices.push(vertex_idx);
     }
     
@@ -129,6 +160,14 @@ pub fn process_single_cell(
 /// * `world_res` – resolution at which geometry is generated.
 /// * `chunk_res` – coarser resolution used to split work into chunks (must be < world_res).
 /// * `output_prefix` – folder and filename prefix for exported files. Results are written to `output/<output_prefix>/`.
+/// * `export_format` – which file(s) to write per chunk (glTF, GeoJSON, or both).
+/// * `color_mode` – per-vertex coloring scheme applied before export.
+/// * `repair_mesh` – opt-in validate-and-repair pass run on each chunk mesh before export.
+/// * `validate_mesh` – opt-in validation report (non-finite vertices, degenerate
+///   triangles, orphan vertices, near-duplicate vertices) printed for each chunk mesh
+///   before export, without fixing anything.
+/// * `lod_ratios` – when present and `export_format` includes glTF, also writes a `-lods` glTF per chunk via `generate_lod_chain`/`export_gltf_lods`, one ratio per LOD level.
+/// * `emit_meshlets` – when set and `export_format` includes glTF, also writes a `-meshlets` glTF per chunk via `build_default_meshlets`/`export_gltf_meshlets`.
 ///
 /// The function prints progress to stdout and returns aggregated statistics on success.
 pub fn gen_world_chunks(
@@ -136,6 +175,12 @@ pub fn gen_world_chunks(
     world_res: u8,
     chunk_res: u8,
     output_prefix: &str,
+    export_format: ExportFormat,
+    color_mode: ColorMode,
+    repair_mesh: bool,
+    validate_mesh: bool,
+    lod_ratios: Option<&[f32]>,
+    emit_meshlets: bool,
 ) -> Result<ProcessingStats, Box<dyn std::error::Error>> {
     if chunk_res >= world_res {
         return Err(format!(
@@ -144,19 +189,11 @@ pub fn gen_world_chunks(
         ).into());
     }
 
-    let chunk_res_enum = h3o::Resolution::try_from(chunk_res)
-        .map_err(|_| format!("Invalid chunk resolution: {} (must be 0..=15)", chunk_res))?;
     let world_res_enum = h3o::Resolution::try_from(world_res)
         .map_err(|_| format!("Invalid world resolution: {} (must be 0..=15)", world_res))?;
 
     // Gather chunk cells at `chunk_res`.
-    let chunk_cells: Vec<CellIndex> = if chunk_res == 0 {
-        CellIndex::base_cells().collect()
-    } else {
-        CellIndex::base_cells()
-            .flat_map(|base| base.children(chunk_res_enum))
-            .collect()
-    };
+    let chunk_cells = collect_cells_at_resolution(chunk_res)?;
 
     let total_chunks = chunk_cells.len();
 
@@ -221,13 +258,82 @@ pub fn gen_world_chunks(
         global_stats.invalid_coords += chunk_stats.invalid_coords;
         global_stats.cells_processed += chunk_stats.cells_processed;
 
+        apply_color_mode(&mut mesh, color_mode, sphere_radius);
+
+        if validate_mesh {
+            let report = validate(&mesh, REPAIR_WELD_EPSILON);
+            if !report.is_clean() {
+                println!(
+                    "  - Validation: {} non-finite vertices, {} degenerate triangles, {} out-of-range indices, {} orphan vertices, {} near-duplicate vertices",
+                    report.non_finite_vertices.len(),
+                    report.degenerate_triangles.len(),
+                    report.out_of_range_indices.len(),
+                    report.orphan_vertices.len(),
+                    report.duplicate_vertices.len()
+                );
+            }
+        }
+
+        // Malformed geometry (e.g. near the poles, where `lat_lng_to_3d` can produce
+        // problematic coordinates) is cheap to fix here before it ships in an export.
+        if repair_mesh {
+            let repair_counts = repair(&mut mesh, Some(REPAIR_WELD_EPSILON));
+            global_stats.degenerate_triangles_removed += repair_counts.degenerate_triangles_removed;
+            global_stats.orphan_vertices_dropped += repair_counts.orphan_vertices_dropped;
+            global_stats.vertices_welded += repair_counts.vertices_welded;
+
+            if repair_counts.degenerate_triangles_removed > 0
+                || repair_counts.orphan_vertices_dropped > 0
+                || repair_counts.vertices_welded > 0
+            {
+                println!(
+                    "  - Repaired chunk: {} degenerate triangles removed, {} orphan vertices dropped, {} vertices welded",
+                    repair_counts.degenerate_triangles_removed,
+                    repair_counts.orphan_vertices_dropped,
+                    repair_counts.vertices_welded
+                );
+            }
+        }
+
+        mesh.compute_normals();
+
         // Export chunk mesh
         let file_stem = format!("{}-chunk{}", output_prefix, chunk_idx + 1);
-        let gltf_path = output_dir.join(format!("{}.gltf", file_stem));
-        let bin_path = output_dir.join(format!("{}.bin", file_stem));
 
-        export_gltf(&mesh, gltf_path.to_str().unwrap(), bin_path.to_str().unwrap())?;
-        println!("  -> Exported {} & {}", gltf_path.display(), bin_path.display());
+        if matches!(export_format, ExportFormat::Gltf | ExportFormat::Both) {
+            let gltf_path = output_dir.join(format!("{}.gltf", file_stem));
+            let bin_path = output_dir.join(format!("{}.bin", file_stem));
+
+            export_gltf(&mesh, gltf_path.to_str().unwrap(), bin_path.to_str().unwrap())?;
+            println!("  -> Exported {} & {}", gltf_path.display(), bin_path.display());
+        }
+
+        if matches!(export_format, ExportFormat::GeoJson | ExportFormat::Both) {
+            let geojson_path = output_dir.join(format!("{}.geojson", file_stem));
+
+            export_geojson(&children, chunk_idx, geojson_path.to_str().unwrap())?;
+            println!("  -> Exported {}", geojson_path.display());
+        }
+
+        if matches!(export_format, ExportFormat::Gltf | ExportFormat::Both) {
+            if let Some(ratios) = lod_ratios {
+                let lods = generate_lod_chain(&mesh, ratios);
+                let lods_gltf_path = output_dir.join(format!("{}-lods.gltf", file_stem));
+                let lods_bin_path = output_dir.join(format!("{}-lods.bin", file_stem));
+
+                export_gltf_lods(&lods, lods_gltf_path.to_str().unwrap(), lods_bin_path.to_str().unwrap())?;
+                println!("  -> Exported {} & {}", lods_gltf_path.display(), lods_bin_path.display());
+            }
+
+            if emit_meshlets {
+                let meshlets = build_default_meshlets(&mesh)?;
+                let meshlets_gltf_path = output_dir.join(format!("{}-meshlets.gltf", file_stem));
+                let meshlets_bin_path = output_dir.join(format!("{}-meshlets.bin", file_stem));
+
+                export_gltf_meshlets(&mesh, &meshlets, meshlets_gltf_path.to_str().unwrap(), meshlets_bin_path.to_str().unwrap())?;
+                println!("  -> Exported {} & {}", meshlets_gltf_path.display(), meshlets_bin_path.display());
+            }
+        }
 
         processed_cells_total += total_cells_in_chunk;
         println!(